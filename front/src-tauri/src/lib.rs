@@ -1,8 +1,11 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use mlua::{HookTriggers, Lua, Value as LuaValue};
 use tauri::Emitter;
 
 #[tauri::command]
@@ -10,10 +13,284 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Minimum stable run time before the restart backoff resets to its initial delay.
+const CV_STABLE_RUN: Duration = Duration::from_secs(10);
+/// Initial restart backoff delay, doubled on each consecutive crash up to `CV_BACKOFF_CAP`.
+const CV_BACKOFF_INITIAL: Duration = Duration::from_millis(250);
+/// Upper bound on the restart backoff delay.
+const CV_BACKOFF_CAP: Duration = Duration::from_secs(5);
+
 /// Session state: CV process (stdout → app) + any session scripts (e.g. data cleaning) started from session_config.json.
 struct SessionState {
     cv_child: Mutex<Option<Child>>,
     session_script_children: Mutex<Vec<Child>>,
+    /// Set while the supervisor loop should keep the CV process alive; cleared by `stop_cv_feed`
+    /// so the supervisor can tell an operator stop apart from a crash.
+    cv_should_run: Arc<AtomicBool>,
+    /// Bumped by every `start_cv_feed`; the supervisor/watcher threads it spawns capture the new
+    /// value and exit once it no longer matches `SessionState`'s, even if `cv_should_run` has
+    /// already flipped back to `true` by a fast stop-then-start. Without this, a stop immediately
+    /// followed by a start (e.g. via the HTTP API) can resurrect the old threads inside the
+    /// 150-200ms poll window before they notice `cv_should_run` went false, leaving two
+    /// supervisors/watchers alive against the same `SessionState`.
+    cv_generation: Arc<AtomicU64>,
+    /// Loaded `session.lua` coaching rules, if the repo root has one. Re-loaded on every `start_cv_feed`.
+    lua_rules: Mutex<Option<LuaRules>>,
+    /// Set once, in `run()`'s `setup` hook, if `session_config.json`'s `http_api.enabled` is set;
+    /// the admin server's lifecycle is independent of the CV feed, so nothing ever clears this
+    /// back to `false` again — it lives for the app's lifetime, not `stop_cv_feed`'s.
+    http_should_run: Arc<AtomicBool>,
+    /// Which CV transport the current (or next) child uses: true for the legacy base64-line
+    /// compatibility path, false for the default length-prefixed binary frames.
+    cv_base64: AtomicBool,
+    /// Channel opened by the frontend (via `register_cv_frame_channel`) for raw binary frame
+    /// delivery; `spawn_cv_binary_reader` sends each frame down it instead of `Emitter::emit`,
+    /// which would serialize a `Vec<u8>` as a JSON array of decimal integers.
+    cv_frame_channel: Mutex<Option<tauri::ipc::Channel<Vec<u8>>>>,
+}
+
+/// Open (or replace) the binary channel `spawn_cv_binary_reader` streams raw CV frames down.
+/// The frontend calls this once, before `start_cv_feed`, to get a real binary transport instead
+/// of a JSON-encoded `cv-frame` event.
+#[tauri::command]
+fn register_cv_frame_channel(
+    state: tauri::State<'_, SessionState>,
+    channel: tauri::ipc::Channel<Vec<u8>>,
+) -> Result<(), String> {
+    *state.cv_frame_channel.lock().map_err(|e| e.to_string())? = Some(channel);
+    Ok(())
+}
+
+/// Max wall-clock time a single `on_rep`/`on_metrics` call may run before it's aborted.
+const LUA_CALL_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// A sandboxed Lua engine loaded from `session.lua`, plus whichever of `on_rep`/`on_metrics`
+/// it defined. `mlua`'s `send` feature makes `Lua` safe to hand to the reader thread behind a mutex.
+struct LuaRules {
+    lua: Lua,
+    on_rep: Option<mlua::Function>,
+    on_metrics: Option<mlua::Function>,
+}
+
+/// Load and sandbox `session.lua` from the repo root. Returns `None` if the file doesn't exist
+/// or fails to load/sandbox, since the coaching layer is optional.
+fn load_session_lua(root: &Path) -> Option<LuaRules> {
+    let path = root.join("session.lua");
+    let source = std::fs::read_to_string(&path).ok()?;
+
+    let lua = Lua::new();
+    // No filesystem/process access, and no way to pull in arbitrary native modules or bytecode:
+    // nil out os/io plus anything that can load code or modules on a script's behalf.
+    let globals = lua.globals();
+    for name in ["os", "io", "package", "require", "load", "loadstring", "dofile", "loadfile"] {
+        let _ = globals.set(name, LuaValue::Nil);
+    }
+
+    lua.load(&source).exec().ok()?;
+
+    let on_rep = globals.get::<mlua::Function>("on_rep").ok();
+    let on_metrics = globals.get::<mlua::Function>("on_metrics").ok();
+    Some(LuaRules { lua, on_rep, on_metrics })
+}
+
+/// Call a Lua callback with a wall-clock timeout, so a misbehaving script can't hang the reader thread.
+fn call_lua_with_timeout(lua: &Lua, func: &mlua::Function, arg: LuaValue) -> mlua::Result<mlua::MultiValue> {
+    let deadline = Instant::now() + LUA_CALL_TIMEOUT;
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(1000),
+        move |_lua, _debug| {
+            if Instant::now() >= deadline {
+                Err(mlua::Error::RuntimeError("session.lua call timed out".into()))
+            } else {
+                Ok(())
+            }
+        },
+    );
+    let result = func.call::<mlua::MultiValue>(arg);
+    lua.remove_hook();
+    result
+}
+
+/// Convert a `serde_json::Value` into an owned Lua value (tables for arrays/objects).
+fn json_to_lua(lua: &Lua, value: &serde_json::Value) -> mlua::Result<LuaValue> {
+    match value {
+        serde_json::Value::Null => Ok(LuaValue::Nil),
+        serde_json::Value::Bool(b) => Ok(LuaValue::Boolean(*b)),
+        serde_json::Value::Number(n) => Ok(n
+            .as_f64()
+            .map(LuaValue::Number)
+            .unwrap_or(LuaValue::Nil)),
+        serde_json::Value::String(s) => lua.create_string(s).map(LuaValue::String),
+        serde_json::Value::Array(items) => {
+            let table = lua.create_table()?;
+            for (i, item) in items.iter().enumerate() {
+                table.set(i + 1, json_to_lua(lua, item)?)?;
+            }
+            Ok(LuaValue::Table(table))
+        }
+        serde_json::Value::Object(map) => {
+            let table = lua.create_table()?;
+            for (k, v) in map {
+                table.set(k.as_str(), json_to_lua(lua, v)?)?;
+            }
+            Ok(LuaValue::Table(table))
+        }
+    }
+}
+
+/// Pull `(verdict, message)` out of a Lua callback's return values and emit them as `rep-feedback`.
+fn emit_lua_verdict(app: &tauri::AppHandle, returned: mlua::MultiValue) {
+    let mut iter = returned.into_iter();
+    let verdict = match iter.next() {
+        Some(LuaValue::String(s)) => s.to_str().unwrap_or("warn").to_string(),
+        _ => return,
+    };
+    let message = match iter.next() {
+        Some(LuaValue::String(s)) => s.to_str().unwrap_or("").to_string(),
+        _ => String::new(),
+    };
+    let _ = app.emit(
+        "rep-feedback",
+        serde_json::json!({ "verdict": verdict, "message": message }),
+    );
+}
+
+/// How long `cv/session_live.json`'s mtime must stay unchanged before `metrics-updated` fires,
+/// so a burst of writes collapses into a single event.
+const METRICS_DEBOUNCE: Duration = Duration::from_millis(150);
+/// Poll interval for the tail/watch loop.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Read any complete lines appended to `path` since `*offset`, advancing `*offset` past them.
+/// Mirrors the CV stdout reader: a `BufReader` over the file, not a full re-read/re-parse.
+/// A partial trailing line (writer mid-write) is left for the next poll.
+fn tail_new_lines(path: &Path, offset: &mut u64) -> Vec<String> {
+    use std::io::{Seek, SeekFrom};
+
+    let mut file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    let len = match file.metadata() {
+        Ok(m) => m.len(),
+        Err(_) => return Vec::new(),
+    };
+    if len < *offset {
+        // Log was truncated or rotated; start over from the top.
+        *offset = 0;
+    }
+    if file.seek(SeekFrom::Start(*offset)).is_err() {
+        return Vec::new();
+    }
+
+    let mut reader = BufReader::new(file);
+    let mut lines = Vec::new();
+    loop {
+        let mut buf = String::new();
+        match reader.read_line(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if !buf.ends_with('\n') {
+                    break;
+                }
+                *offset += n as u64;
+                let trimmed = buf.trim_end();
+                if !trimmed.is_empty() {
+                    lines.push(trimmed.to_string());
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    lines
+}
+
+/// Tail `cv/reps_log.jsonl` and watch `cv/session_live.json` for the lifetime of the CV session:
+/// emit `rep-logged` per new rep entry and a debounced `metrics-updated` on metrics changes, and
+/// (when `session.lua` defined them) feed the same data to `on_rep`/`on_metrics`. The existing
+/// `get_rep_count`/`get_live_metrics` commands remain the fallback for the frontend's initial load;
+/// once running, it should receive these deltas instead of repolling and reparsing the whole log.
+/// Exits once `cv_should_run` clears or `cv_generation` moves past `generation` (see
+/// `supervise_cv_child`'s `is_current` for why generation, not just the flag, matters).
+fn run_session_watcher_loop(app: tauri::AppHandle, generation: u64) {
+    use tauri::Manager;
+
+    let Ok(root) = repo_root() else { return };
+    let reps_path = root.join("cv/reps_log.jsonl");
+    let metrics_path = root.join("cv/session_live.json");
+    // Seed from what's already on disk rather than 0/UNIX_EPOCH: a restart (crash-free, or a
+    // stop/start cycle via the HTTP API) must not re-tail history the frontend already saw and
+    // re-fire on_rep/rep-feedback for reps that happened minutes ago.
+    let mut reps_offset = std::fs::metadata(&reps_path).map(|m| m.len()).unwrap_or(0);
+    let mut metrics_last_emitted = std::fs::metadata(&metrics_path)
+        .and_then(|m| m.modified())
+        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    let mut metrics_pending: Option<(std::time::SystemTime, Instant)> = None;
+
+    loop {
+        let state = app.state::<SessionState>();
+        if !state.cv_should_run.load(Ordering::SeqCst)
+            || state.cv_generation.load(Ordering::SeqCst) != generation
+        {
+            return;
+        }
+
+        for line in tail_new_lines(&reps_path, &mut reps_offset) {
+            let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            let _ = app.emit("rep-logged", &entry);
+
+            if let Ok(guard) = state.lua_rules.lock() {
+                if let Some(rules) = guard.as_ref() {
+                    if let Some(on_rep) = &rules.on_rep {
+                        if let Ok(arg) = json_to_lua(&rules.lua, &entry) {
+                            if let Ok(ret) = call_lua_with_timeout(&rules.lua, on_rep, arg) {
+                                emit_lua_verdict(&app, ret);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Ok(meta) = std::fs::metadata(&metrics_path) {
+            if let Ok(modified) = meta.modified() {
+                if modified != metrics_last_emitted {
+                    match metrics_pending {
+                        Some((pending_mtime, _)) if pending_mtime == modified => {}
+                        _ => metrics_pending = Some((modified, Instant::now())),
+                    }
+                }
+            }
+        }
+
+        if let Some((pending_mtime, seen_at)) = metrics_pending {
+            if seen_at.elapsed() >= METRICS_DEBOUNCE {
+                metrics_pending = None;
+                metrics_last_emitted = pending_mtime;
+                if let Ok(content) = std::fs::read_to_string(&metrics_path) {
+                    if let Ok(metrics) = serde_json::from_str::<serde_json::Value>(&content) {
+                        let _ = app.emit("metrics-updated", &metrics);
+
+                        if let Ok(guard) = state.lua_rules.lock() {
+                            if let Some(rules) = guard.as_ref() {
+                                if let Some(on_metrics) = &rules.on_metrics {
+                                    if let Ok(arg) = json_to_lua(&rules.lua, &metrics) {
+                                        if let Ok(ret) = call_lua_with_timeout(&rules.lua, on_metrics, arg) {
+                                            emit_lua_verdict(&app, ret);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+    }
 }
 
 fn repo_root() -> Result<std::path::PathBuf, String> {
@@ -33,6 +310,238 @@ fn cv_stdout_frames_path() -> Result<std::path::PathBuf, String> {
         .map_err(|e| format!("cv_stdout_frames.py not found at {}: {}", script.display(), e))
 }
 
+/// Spawn the cv_stdout_frames.py child, trying `python3` then falling back to `python`.
+/// Spawn the cv_stdout_frames.py child. By default it streams length-prefixed binary frames;
+/// pass `base64` to add the legacy `--base64` flag for the line-based compatibility transport.
+fn spawn_cv_child(root: &Path, script_path: &Path, base64: bool) -> Result<Child, String> {
+    let base64_arg: &[&str] = if base64 { &["--base64"] } else { &[] };
+    Command::new("python3")
+        .arg(script_path)
+        .args(base64_arg)
+        .current_dir(root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .or_else(|_| {
+            Command::new("python")
+                .arg(script_path)
+                .args(base64_arg)
+                .current_dir(root)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .spawn()
+        })
+        .map_err(|e| format!("Failed to run cv.py pipeline: {}", e))
+}
+
+/// Spawn the reader thread that forwards the child's base64 stdout lines as `cv-frame` events
+/// (legacy transport; see `spawn_cv_binary_reader` for the default length-prefixed transport).
+fn spawn_cv_stdout_reader(app: tauri::AppHandle, stdout: std::process::ChildStdout) {
+    std::thread::spawn(move || {
+        let reader = BufReader::new(stdout);
+        for line in reader.lines() {
+            match line {
+                Ok(b64) => {
+                    let _ = app.emit("cv-frame", &b64);
+                }
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// How often a running reader reports `frame_bytes`/`frame_latency_ms` on `cv-status`.
+const CV_FRAME_STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Upper bound on a single binary frame. Without this, a desynced stream (a dropped/extra byte
+/// from the child, a crash mid-frame) lets 4 arbitrary bytes be read as a length and trigger an
+/// allocation up to ~4GB before `read_exact` even gets a chance to fail.
+const CV_MAX_FRAME_BYTES: usize = 32 * 1024 * 1024;
+
+/// Read one length-prefixed frame (4-byte big-endian length, then that many bytes) from `reader`.
+/// Returns `Ok(None)` on a clean EOF before any of the length prefix arrives; an oversized length
+/// (see `CV_MAX_FRAME_BYTES`) is treated the same as a read error, since it almost always means
+/// the stream desynced rather than that a frame is legitimately that large.
+fn read_cv_frame<R: Read>(reader: &mut R) -> std::io::Result<Option<Vec<u8>>> {
+    let mut len_buf = [0u8; 4];
+    match reader.read_exact(&mut len_buf) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len > CV_MAX_FRAME_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("cv frame length {} exceeds max {}", len, CV_MAX_FRAME_BYTES),
+        ));
+    }
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    Ok(Some(payload))
+}
+
+/// Spawn the reader thread for the default transport: cv_stdout_frames.py writes each frame as a
+/// 4-byte big-endian length prefix followed by that many bytes of raw JPEG/PNG, and this reads
+/// with `read_exact` rather than `lines()` so there's no UTF-8/line-buffering ceiling on throughput.
+/// Frames are handed to whichever `tauri::ipc::Channel<Vec<u8>>` the frontend registered via
+/// `register_cv_frame_channel`; a plain `Emitter::emit` would JSON-serialize the `Vec<u8>` into a
+/// decimal-integer array, which is worse than the base64 text this transport replaces.
+fn spawn_cv_binary_reader(app: tauri::AppHandle, stdout: std::process::ChildStdout) {
+    use tauri::Manager;
+
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut last_status_at = Instant::now() - CV_FRAME_STATUS_INTERVAL;
+        loop {
+            let frame_start = Instant::now();
+            let payload = match read_cv_frame(&mut reader) {
+                Ok(Some(payload)) => payload,
+                Ok(None) | Err(_) => break,
+            };
+            let frame_bytes = payload.len();
+
+            if let Ok(guard) = app.state::<SessionState>().cv_frame_channel.lock() {
+                if let Some(channel) = guard.as_ref() {
+                    let _ = channel.send(payload);
+                }
+            }
+
+            if last_status_at.elapsed() >= CV_FRAME_STATUS_INTERVAL {
+                last_status_at = Instant::now();
+                let _ = app.emit(
+                    "cv-status",
+                    serde_json::json!({
+                        "state": "running",
+                        "code": null,
+                        "frame_bytes": frame_bytes,
+                        "frame_latency_ms": frame_start.elapsed().as_millis(),
+                    }),
+                );
+            }
+        }
+    });
+}
+
+/// Supervise the CV child: wait for it to exit, emit `cv-status` transitions, and restart it
+/// with exponential backoff until `stop_cv_feed` clears `cv_should_run` or `start_cv_feed` bumps
+/// `cv_generation` past the value this thread was spawned with (see `generation`/`is_current`).
+fn supervise_cv_child(app: tauri::AppHandle, generation: u64) {
+    use tauri::Manager;
+
+    // True only while this is still the generation start_cv_feed most recently bumped to; a
+    // stop-then-start close enough together can flip cv_should_run back to true before this
+    // thread's next poll notices it went false, so generation (not just cv_should_run) is what
+    // tells a stale supervisor it's time to exit instead of running alongside a fresh one.
+    let is_current = |state: &tauri::State<'_, SessionState>| {
+        state.cv_should_run.load(Ordering::SeqCst)
+            && state.cv_generation.load(Ordering::SeqCst) == generation
+    };
+
+    let mut backoff = CV_BACKOFF_INITIAL;
+    loop {
+        let started_at = Instant::now();
+        let state = app.state::<SessionState>();
+
+        // Poll for exit instead of blocking wait() so stop_cv_feed can still lock cv_child to kill it.
+        let exit_code = loop {
+            if !is_current(&state) {
+                return;
+            }
+            let mut guard = match state.cv_child.lock() {
+                Ok(g) => g,
+                Err(_) => return,
+            };
+            match guard.as_mut() {
+                Some(child) => match child.try_wait() {
+                    Ok(Some(status)) => break status.code(),
+                    Ok(None) => {}
+                    Err(_) => break None,
+                },
+                None => return,
+            }
+            drop(guard);
+            std::thread::sleep(Duration::from_millis(200));
+        };
+
+        // The process that just exited is still sitting in cv_child; clear it out.
+        {
+            if let Ok(mut guard) = state.cv_child.lock() {
+                guard.take();
+            }
+        }
+
+        if !is_current(&state) {
+            return;
+        }
+
+        let _ = app.emit(
+            "cv-status",
+            serde_json::json!({ "state": "exited", "code": exit_code }),
+        );
+
+        if started_at.elapsed() >= CV_STABLE_RUN {
+            backoff = CV_BACKOFF_INITIAL;
+        }
+
+        let _ = app.emit(
+            "cv-status",
+            serde_json::json!({ "state": "restarting", "code": exit_code }),
+        );
+        std::thread::sleep(backoff);
+        backoff = std::cmp::min(backoff * 2, CV_BACKOFF_CAP);
+
+        if !is_current(&state) {
+            return;
+        }
+
+        let root = match repo_root() {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        let script_path = match cv_stdout_frames_path() {
+            Ok(p) => p,
+            Err(_) => return,
+        };
+        let base64 = state.cv_base64.load(Ordering::SeqCst);
+        // Keep retrying the respawn itself (with backoff) on failure, rather than falling through
+        // to the exit-wait loop above with cv_child still None: that loop treats a None child as
+        // "already torn down" and returns, which would silently kill this supervisor forever after
+        // a single transient spawn failure.
+        let mut child = loop {
+            if !is_current(&state) {
+                return;
+            }
+            match spawn_cv_child(&root, &script_path, base64) {
+                Ok(c) if c.stdout.is_some() => break c,
+                Ok(mut c) => {
+                    let _ = c.kill();
+                }
+                Err(_) => {}
+            }
+            let _ = app.emit(
+                "cv-status",
+                serde_json::json!({ "state": "restarting", "code": null }),
+            );
+            std::thread::sleep(backoff);
+            backoff = std::cmp::min(backoff * 2, CV_BACKOFF_CAP);
+        };
+        let stdout = child.stdout.take().expect("checked Some above");
+
+        if let Ok(mut guard) = state.cv_child.lock() {
+            *guard = Some(child);
+        } else {
+            return;
+        }
+        if base64 {
+            spawn_cv_stdout_reader(app.clone(), stdout);
+        } else {
+            spawn_cv_binary_reader(app.clone(), stdout);
+        }
+        let _ = app.emit("cv-status", serde_json::json!({ "state": "running", "code": null }));
+    }
+}
+
 /// Start CV pipeline (output → app) and any session_scripts from session_config.json (repo root).
 #[tauri::command]
 fn start_cv_feed(app: tauri::AppHandle, state: tauri::State<'_, SessionState>) -> Result<(), String> {
@@ -45,69 +554,81 @@ fn start_cv_feed(app: tauri::AppHandle, state: tauri::State<'_, SessionState>) -
     }
 
     let root = repo_root()?;
+    let cfg = read_session_config(&root);
     let script_path = cv_stdout_frames_path()?;
-    let mut child = Command::new("python3")
-        .arg(&script_path)
-        .current_dir(&root)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .or_else(|_| {
-            Command::new("python")
-                .arg(&script_path)
-                .current_dir(&root)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::inherit())
-                .spawn()
-        })
-        .map_err(|e| format!("Failed to run cv.py pipeline: {}", e))?;
+    state.cv_base64.store(cfg.cv_base64, Ordering::SeqCst);
+    let mut child = spawn_cv_child(&root, &script_path, cfg.cv_base64)?;
 
     let stdout = child
         .stdout
         .take()
         .ok_or_else(|| "No stdout from cv process".to_string())?;
 
+    // Bump the generation before spawning anything: any supervisor/watcher thread from a previous
+    // run (e.g. one that hasn't yet noticed a stop that happened moments ago) captures the old
+    // value and will exit as stale rather than race the threads spawned below.
+    let generation = state.cv_generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    state.cv_should_run.store(true, Ordering::SeqCst);
     {
         let mut guard = state.cv_child.lock().map_err(|e| e.to_string())?;
         *guard = Some(child);
     }
 
-    let app = app.clone();
-    std::thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            match line {
-                Ok(b64) => {
-                    let _ = app.emit("cv-frame", &b64);
-                }
-                Err(_) => break,
-            }
-        }
-    });
+    if cfg.cv_base64 {
+        spawn_cv_stdout_reader(app.clone(), stdout);
+    } else {
+        spawn_cv_binary_reader(app.clone(), stdout);
+    }
+    let _ = app.emit("cv-status", serde_json::json!({ "state": "running", "code": null }));
+
+    {
+        let app = app.clone();
+        std::thread::spawn(move || supervise_cv_child(app, generation));
+    }
+
+    // Load session.lua (optional coaching rules), if present, for the watcher loop below to drive.
+    *state.lua_rules.lock().map_err(|e| e.to_string())? = load_session_lua(&root);
+
+    // Tail reps/metrics for rep-logged/metrics-updated events (and feed session.lua, if loaded).
+    {
+        let app = app.clone();
+        std::thread::spawn(move || run_session_watcher_loop(app, generation));
+    }
 
     // Start session scripts from session_config.json (e.g. data cleaning)
-    let config_path = root.join("session_config.json");
-    if let Ok(buf) = std::fs::read_to_string(&config_path) {
-        if let Ok(cfg) = serde_json::from_str::<SessionConfig>(&buf) {
-            let mut children = state.session_script_children.lock().map_err(|e| e.to_string())?;
-            for cmd in cfg.session_scripts {
-                if cmd.is_empty() {
-                    continue;
-                }
-                // Run first token as program, rest as args (e.g. "python ProcessedData/synthesizer.py")
-                let parts: Vec<&str> = cmd.split_whitespace().collect();
-                if parts.is_empty() {
-                    continue;
-                }
-                let c = Command::new(parts[0])
-                    .args(parts.iter().skip(1))
-                    .current_dir(&root)
-                    .stdout(Stdio::null())
-                    .stderr(Stdio::null())
-                    .spawn();
-                if let Ok(proc) = c {
+    {
+        let mut children = state.session_script_children.lock().map_err(|e| e.to_string())?;
+        for spec in cfg.session_scripts {
+            let Some(script) = SessionScript::from_spec(spec) else {
+                continue;
+            };
+            let cwd = script
+                .cwd
+                .as_ref()
+                .map(|c| root.join(c))
+                .unwrap_or_else(|| root.clone());
+            let stdio = || if script.emit_output { Stdio::piped() } else { Stdio::null() };
+            let mut command = Command::new(&script.program);
+            command
+                .args(&script.args)
+                .envs(&script.env)
+                .current_dir(&cwd)
+                .stdout(stdio())
+                .stderr(stdio());
+            match command.spawn() {
+                Ok(mut proc) => {
+                    if script.emit_output {
+                        if let Some(stdout) = proc.stdout.take() {
+                            spawn_session_script_log_reader(app.clone(), script.name.clone(), "stdout", stdout);
+                        }
+                        if let Some(stderr) = proc.stderr.take() {
+                            spawn_session_script_log_reader(app.clone(), script.name.clone(), "stderr", stderr);
+                        }
+                    }
                     children.push(proc);
                 }
+                Err(_) => continue,
             }
         }
     }
@@ -115,15 +636,186 @@ fn start_cv_feed(app: tauri::AppHandle, state: tauri::State<'_, SessionState>) -
     Ok(())
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, Default)]
 struct SessionConfig {
     #[serde(default)]
-    session_scripts: Vec<String>,
+    session_scripts: Vec<SessionScriptSpec>,
+    #[serde(default)]
+    http_api: HttpApiConfig,
+    /// Use the legacy base64-line CV transport instead of length-prefixed binary frames.
+    #[serde(default)]
+    cv_base64: bool,
+}
+
+/// Read and parse `session_config.json` from the repo root; an absent or invalid file just
+/// means every optional feature it can configure stays at its default.
+fn read_session_config(root: &Path) -> SessionConfig {
+    std::fs::read_to_string(root.join("session_config.json"))
+        .ok()
+        .and_then(|buf| serde_json::from_str(&buf).ok())
+        .unwrap_or_default()
+}
+
+/// `http_api` section of session_config.json: the optional localhost admin API.
+#[derive(serde::Deserialize, Default)]
+struct HttpApiConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_http_api_port")]
+    port: u16,
+    /// Required to authorize `POST /workout`, `/start`, `/stop`; writes are refused if unset.
+    #[serde(default)]
+    token: Option<String>,
+}
+
+fn default_http_api_port() -> u16 {
+    4949
+}
+
+/// One `session_scripts` entry: either the legacy shell-string form (parsed with
+/// `parse_shell_words`) or the structured form with explicit program/args/env/cwd.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SessionScriptSpec {
+    Legacy(String),
+    Structured {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        env: std::collections::HashMap<String, String>,
+        #[serde(default)]
+        cwd: Option<String>,
+        #[serde(default)]
+        emit_output: bool,
+    },
+}
+
+/// A resolved session script ready to spawn.
+struct SessionScript {
+    /// Tag used on `session-script-log` events; defaults to the program's file stem.
+    name: String,
+    program: String,
+    args: Vec<String>,
+    env: std::collections::HashMap<String, String>,
+    cwd: Option<String>,
+    emit_output: bool,
+}
+
+impl SessionScript {
+    fn from_spec(spec: SessionScriptSpec) -> Option<Self> {
+        match spec {
+            SessionScriptSpec::Legacy(cmd) => {
+                let mut tokens = parse_shell_words(&cmd).into_iter();
+                let program = tokens.next()?;
+                let name = Path::new(&program)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| program.clone());
+                Some(SessionScript {
+                    name,
+                    program,
+                    args: tokens.collect(),
+                    env: std::collections::HashMap::new(),
+                    cwd: None,
+                    emit_output: false,
+                })
+            }
+            SessionScriptSpec::Structured { program, args, env, cwd, emit_output } => {
+                let name = Path::new(&program)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| program.clone());
+                Some(SessionScript { name, program, args, env, cwd, emit_output })
+            }
+        }
+    }
+}
+
+/// Split a command string into program/args, honoring single and double quotes and
+/// backslash escapes (shell-words style), so paths/args containing spaces survive.
+fn parse_shell_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                } else if c == '\\' && q == '"' {
+                    if let Some(&next) = chars.peek() {
+                        if next == '"' || next == '\\' {
+                            current.push(chars.next().unwrap());
+                            continue;
+                        }
+                    }
+                    current.push(c);
+                } else {
+                    current.push(c);
+                }
+            }
+            None => match c {
+                ' ' | '\t' => {
+                    if in_word {
+                        words.push(std::mem::take(&mut current));
+                        in_word = false;
+                    }
+                }
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                '\\' => {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                        in_word = true;
+                    }
+                }
+                _ => {
+                    current.push(c);
+                    in_word = true;
+                }
+            },
+        }
+    }
+    if in_word || !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Forward a session script's stdout/stderr lines as `session-script-log` events tagged with its name.
+fn spawn_session_script_log_reader<R: std::io::Read + Send + 'static>(
+    app: tauri::AppHandle,
+    script_name: String,
+    stream: &'static str,
+    reader: R,
+) {
+    std::thread::spawn(move || {
+        let buf = BufReader::new(reader);
+        for line in buf.lines() {
+            match line {
+                Ok(text) => {
+                    let _ = app.emit(
+                        "session-script-log",
+                        serde_json::json!({ "script": script_name, "stream": stream, "line": text }),
+                    );
+                }
+                Err(_) => break,
+            }
+        }
+    });
 }
 
 /// Stop CV pipeline and all session scripts.
 #[tauri::command]
 fn stop_cv_feed(state: tauri::State<'_, SessionState>) -> Result<(), String> {
+    // Flip this before killing the child so the supervisor sees an operator stop, not a crash.
+    state.cv_should_run.store(false, Ordering::SeqCst);
     {
         let mut guard = state.cv_child.lock().map_err(|e| e.to_string())?;
         if let Some(mut child) = guard.take() {
@@ -134,9 +826,130 @@ fn stop_cv_feed(state: tauri::State<'_, SessionState>) -> Result<(), String> {
     for mut child in guard.drain(..) {
         let _ = child.kill();
     }
+    drop(guard);
+    state.lua_rules.lock().map_err(|e| e.to_string())?.take();
     Ok(())
 }
 
+/// Run the optional localhost admin API for the app's lifetime (started once from `run()` if
+/// `session_config.json`'s `http_api.enabled` is set; independent of `cv_should_run`, so `/start`
+/// stays reachable after a `/stop` and a `/stop` doesn't take the server down with the session).
+/// Binds only to 127.0.0.1; `GET /reps` and `GET /metrics` mirror `get_rep_count`/`get_live_metrics`,
+/// `POST /workout` mirrors `write_workout_id`, and `POST /start`/`POST /stop` drive the CV feed itself.
+fn run_http_api_server(app: tauri::AppHandle, config: HttpApiConfig, should_run: Arc<AtomicBool>) {
+    let server = match tiny_http::Server::http(("127.0.0.1", config.port)) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    while should_run.load(Ordering::SeqCst) {
+        match server.recv_timeout(Duration::from_millis(200)) {
+            Ok(Some(request)) => handle_http_api_request(&app, &config, request),
+            Ok(None) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+/// Does the request carry `Authorization: Bearer <config.token>`? Writes are refused (fail closed)
+/// if no token is configured.
+fn http_api_authorized(config: &HttpApiConfig, request: &tiny_http::Request) -> bool {
+    let Some(expected) = &config.token else {
+        return false;
+    };
+    let wanted = format!("Bearer {}", expected);
+    request
+        .headers()
+        .iter()
+        .any(|h| h.field.equiv("Authorization") && h.value == wanted.as_str())
+}
+
+fn http_api_json_response<T: serde::Serialize>(request: tiny_http::Request, body: &T) {
+    let json = serde_json::to_string(body).unwrap_or_else(|_| "null".to_string());
+    let header = tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    let _ = request.respond(tiny_http::Response::from_string(json).with_header(header));
+}
+
+fn http_api_result_response(request: tiny_http::Request, result: Result<(), String>) {
+    match result {
+        Ok(()) => http_api_json_response(request, &serde_json::json!({ "ok": true })),
+        Err(message) => {
+            let response = tiny_http::Response::from_string(message)
+                .with_status_code(tiny_http::StatusCode(500));
+            let _ = request.respond(response);
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct HttpApiWorkoutBody {
+    workout_id: String,
+    session: String,
+}
+
+fn handle_http_api_request(app: &tauri::AppHandle, config: &HttpApiConfig, mut request: tiny_http::Request) {
+    use tauri::Manager;
+
+    let method = request.method().clone();
+    let url = request.url().to_string();
+
+    let needs_auth = matches!(
+        (&method, url.as_str()),
+        (tiny_http::Method::Post, "/workout" | "/start" | "/stop")
+    );
+    if needs_auth && !http_api_authorized(config, &request) {
+        let response =
+            tiny_http::Response::from_string("unauthorized").with_status_code(tiny_http::StatusCode(401));
+        let _ = request.respond(response);
+        return;
+    }
+
+    match (method, url.as_str()) {
+        (tiny_http::Method::Get, "/reps") => match get_rep_count() {
+            Ok(body) => http_api_json_response(request, &body),
+            Err(e) => http_api_result_response(request, Err(e)),
+        },
+        (tiny_http::Method::Get, "/metrics") => match get_live_metrics() {
+            Ok(body) => http_api_json_response(request, &body),
+            Err(e) => http_api_result_response(request, Err(e)),
+        },
+        (tiny_http::Method::Post, "/workout") => {
+            let mut raw = String::new();
+            if request.as_reader().read_to_string(&mut raw).is_err() {
+                let response =
+                    tiny_http::Response::from_string("bad request").with_status_code(tiny_http::StatusCode(400));
+                let _ = request.respond(response);
+                return;
+            }
+            match serde_json::from_str::<HttpApiWorkoutBody>(&raw) {
+                Ok(payload) => {
+                    let result = write_workout_id(payload.workout_id, payload.session);
+                    http_api_result_response(request, result);
+                }
+                Err(e) => {
+                    let response = tiny_http::Response::from_string(e.to_string())
+                        .with_status_code(tiny_http::StatusCode(400));
+                    let _ = request.respond(response);
+                }
+            }
+        }
+        (tiny_http::Method::Post, "/start") => {
+            let state = app.state::<SessionState>();
+            let result = start_cv_feed(app.clone(), state);
+            http_api_result_response(request, result);
+        }
+        (tiny_http::Method::Post, "/stop") => {
+            let state = app.state::<SessionState>();
+            let result = stop_cv_feed(state);
+            http_api_result_response(request, result);
+        }
+        _ => {
+            let response =
+                tiny_http::Response::from_string("not found").with_status_code(tiny_http::StatusCode(404));
+            let _ = request.respond(response);
+        }
+    }
+}
+
 /// Write workout_id.json as JSONL: one line per state, {"workout_id":"squat","session":"on"} or "off".
 #[tauri::command]
 fn write_workout_id(workout_id: String, session: String) -> Result<(), String> {
@@ -149,6 +962,7 @@ fn write_workout_id(workout_id: String, session: String) -> Result<(), String> {
 }
 
 /// Rep count, last summary, and rep timestamps (session-relative ms) from cv/reps_log.jsonl.
+/// Fallback for the frontend's initial load; once a session is running, prefer the `rep-logged` events.
 #[tauri::command]
 fn get_rep_count() -> Result<RepCountResult, String> {
     let root = repo_root()?;
@@ -200,6 +1014,7 @@ struct RepLogEntry {
 }
 
 /// Live metrics (e.g. Depth, Knees for squat) from cv/session_live.json (written by cv.py when session on).
+/// Fallback for the frontend's initial load; once a session is running, prefer the `metrics-updated` events.
 #[tauri::command]
 fn get_live_metrics() -> Result<Option<serde_json::Value>, String> {
     let root = repo_root()?;
@@ -211,6 +1026,162 @@ fn get_live_metrics() -> Result<Option<serde_json::Value>, String> {
     serde_json::from_str(&content).map(Some).or(Ok(None))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shell_words_splits_on_unquoted_whitespace() {
+        assert_eq!(
+            parse_shell_words("python3 script.py --flag value"),
+            vec!["python3", "script.py", "--flag", "value"],
+        );
+    }
+
+    #[test]
+    fn parse_shell_words_collapses_runs_of_whitespace() {
+        assert_eq!(parse_shell_words("  a   b\tc  "), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn parse_shell_words_keeps_single_quoted_spaces_together() {
+        assert_eq!(
+            parse_shell_words("'My Data/synth.py'"),
+            vec!["My Data/synth.py"],
+        );
+    }
+
+    #[test]
+    fn parse_shell_words_keeps_double_quoted_spaces_together() {
+        assert_eq!(
+            parse_shell_words("python3 \"My Data/synth.py\""),
+            vec!["python3", "My Data/synth.py"],
+        );
+    }
+
+    #[test]
+    fn parse_shell_words_handles_mixed_quoting_in_one_command() {
+        assert_eq!(
+            parse_shell_words("run 'a b' \"c\\\"d\""),
+            vec!["run", "a b", "c\"d"],
+        );
+    }
+
+    #[test]
+    fn parse_shell_words_backslash_escapes_outside_quotes() {
+        assert_eq!(parse_shell_words("a\\ b c"), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn parse_shell_words_single_quotes_do_not_interpret_backslash() {
+        assert_eq!(parse_shell_words("'a\\b'"), vec!["a\\b"]);
+    }
+
+    #[test]
+    fn parse_shell_words_empty_input_yields_no_words() {
+        assert_eq!(parse_shell_words(""), Vec::<String>::new());
+        assert_eq!(parse_shell_words("   "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn parse_shell_words_unterminated_quote_keeps_rest_as_one_word() {
+        assert_eq!(parse_shell_words("a 'b c"), vec!["a", "b c"]);
+    }
+
+    fn frame_bytes(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = (payload.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[test]
+    fn read_cv_frame_returns_payload() {
+        let input = frame_bytes(b"hello");
+        let mut cursor = std::io::Cursor::new(input);
+        assert_eq!(
+            read_cv_frame(&mut cursor).unwrap(),
+            Some(b"hello".to_vec()),
+        );
+    }
+
+    #[test]
+    fn read_cv_frame_reads_consecutive_frames() {
+        let mut input = frame_bytes(b"one");
+        input.extend(frame_bytes(b"two"));
+        let mut cursor = std::io::Cursor::new(input);
+        assert_eq!(read_cv_frame(&mut cursor).unwrap(), Some(b"one".to_vec()));
+        assert_eq!(read_cv_frame(&mut cursor).unwrap(), Some(b"two".to_vec()));
+        assert_eq!(read_cv_frame(&mut cursor).unwrap(), None);
+    }
+
+    #[test]
+    fn read_cv_frame_empty_payload_is_valid() {
+        let input = frame_bytes(b"");
+        let mut cursor = std::io::Cursor::new(input);
+        assert_eq!(read_cv_frame(&mut cursor).unwrap(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn read_cv_frame_clean_eof_before_length_prefix_is_none() {
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        assert_eq!(read_cv_frame(&mut cursor).unwrap(), None);
+    }
+
+    #[test]
+    fn read_cv_frame_truncated_payload_is_an_error() {
+        let mut input = (5u32).to_be_bytes().to_vec();
+        input.extend_from_slice(b"ab"); // claims 5 bytes, only 2 follow
+        let mut cursor = std::io::Cursor::new(input);
+        assert!(read_cv_frame(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn read_cv_frame_oversized_length_is_rejected_before_allocating() {
+        let oversized = (CV_MAX_FRAME_BYTES as u32 + 1).to_be_bytes();
+        let mut cursor = std::io::Cursor::new(oversized.to_vec());
+        assert!(read_cv_frame(&mut cursor).is_err());
+    }
+
+    fn write_temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "kinera_tail_new_lines_test_{}_{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn tail_new_lines_reads_only_new_complete_lines() {
+        let path = write_temp_file("complete", "a\nb\n");
+        let mut offset = 0u64;
+        assert_eq!(tail_new_lines(&path, &mut offset), vec!["a", "b"]);
+        assert_eq!(tail_new_lines(&path, &mut offset), Vec::<String>::new());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tail_new_lines_leaves_a_partial_trailing_line_for_next_poll() {
+        let path = write_temp_file("partial", "a\nb");
+        let mut offset = 0u64;
+        assert_eq!(tail_new_lines(&path, &mut offset), vec!["a"]);
+        std::fs::write(&path, "a\nb\n").unwrap();
+        assert_eq!(tail_new_lines(&path, &mut offset), vec!["b"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tail_new_lines_restarts_from_top_after_truncation() {
+        let path = write_temp_file("truncated", "a\nb\nc\n");
+        let mut offset = 0u64;
+        assert_eq!(tail_new_lines(&path, &mut offset), vec!["a", "b", "c"]);
+        std::fs::write(&path, "x\n").unwrap();
+        assert_eq!(tail_new_lines(&path, &mut offset), vec!["x"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -218,9 +1189,33 @@ pub fn run() {
         .manage(SessionState {
             cv_child: Mutex::new(None),
             session_script_children: Mutex::new(Vec::new()),
+            cv_should_run: Arc::new(AtomicBool::new(false)),
+            cv_generation: Arc::new(AtomicU64::new(0)),
+            lua_rules: Mutex::new(None),
+            http_should_run: Arc::new(AtomicBool::new(false)),
+            cv_base64: AtomicBool::new(false),
+            cv_frame_channel: Mutex::new(None),
+        })
+        .setup(|app| {
+            use tauri::Manager;
+
+            // The admin API's lifecycle is independent of the CV feed (see run_http_api_server):
+            // start it once here if configured, rather than as a side effect of start_cv_feed.
+            if let Ok(root) = repo_root() {
+                let cfg = read_session_config(&root);
+                if cfg.http_api.enabled {
+                    let state = app.state::<SessionState>();
+                    state.http_should_run.store(true, Ordering::SeqCst);
+                    let app_handle = app.handle().clone();
+                    let should_run = state.http_should_run.clone();
+                    std::thread::spawn(move || run_http_api_server(app_handle, cfg.http_api, should_run));
+                }
+            }
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             greet,
+            register_cv_frame_channel,
             start_cv_feed,
             stop_cv_feed,
             write_workout_id,